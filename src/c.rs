@@ -5,12 +5,78 @@ pub enum LedMatrix {}
 pub enum LedCanvas {}
 pub enum LedFont {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LedColor {
     pub red: u8,
     pub green: u8,
     pub blue: u8,
 }
 
+impl LedColor {
+    /// Constructs a colour from hue, saturation and value, each in the
+    /// range `0.0..=1.0` except `h` which wraps over `0.0..360.0` degrees.
+    pub fn from_hsv(h: f64, s: f64, v: f64) -> LedColor {
+        let h = h.rem_euclid(360.0);
+        let c = v * s;
+        let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+        let m = v - c;
+
+        let (r, g, b) = match h as u32 / 60 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+
+        LedColor {
+            red: (((r + m) * 255.0).round()) as u8,
+            green: (((g + m) * 255.0).round()) as u8,
+            blue: (((b + m) * 255.0).round()) as u8,
+        }
+    }
+
+    /// Constructs a colour from a packed `0xRRGGBB` value.
+    pub fn from_hex(hex: u32) -> LedColor {
+        LedColor {
+            red: (hex >> 16) as u8,
+            green: (hex >> 8) as u8,
+            blue: hex as u8,
+        }
+    }
+
+    /// Packs the colour into a `0xRRGGBB` value.
+    pub fn to_hex(&self) -> u32 {
+        (self.red as u32) << 16 | (self.green as u32) << 8 | self.blue as u32
+    }
+
+    /// Linearly interpolates between this colour and `other`, where `t = 0.0`
+    /// returns this colour and `t = 1.0` returns `other`.
+    pub fn lerp(&self, other: &LedColor, t: f64) -> LedColor {
+        let lerp_channel = |a: u8, b: u8| -> u8 {
+            (a as f64 + (b as f64 - a as f64) * t).round().clamp(0.0, 255.0) as u8
+        };
+
+        LedColor {
+            red: lerp_channel(self.red, other.red),
+            green: lerp_channel(self.green, other.green),
+            blue: lerp_channel(self.blue, other.blue),
+        }
+    }
+
+    /// Scales each channel by `factor`, clamping to `0..=255`.
+    pub fn scale_brightness(&self, factor: f64) -> LedColor {
+        let scale_channel = |c: u8| -> u8 { ((c as f64 * factor).round().clamp(0.0, 255.0)) as u8 };
+
+        LedColor {
+            red: scale_channel(self.red),
+            green: scale_channel(self.green),
+            blue: scale_channel(self.blue),
+        }
+    }
+}
+
 type LedMatrixOptionsResult = Result<(), &'static str>;
 
 /// Parameters to create a new matrix.
@@ -325,6 +391,10 @@ extern "C" {
         argc: *mut c_int,
         argv: *mut *mut *mut c_char,
     ) -> *mut LedMatrix;
+    pub fn led_matrix_create_from_options_and_rt_options(
+        options: *const crate::led_matrix_options::Options,
+        rt_options: *const crate::led_matrix_options::RuntimeOptions,
+    ) -> *mut LedMatrix;
     //    pub fn led_matrix_create(
     //        rows: c_int, chained: c_int, parallel: c_int) -> *mut LedMatrix;
     pub fn led_matrix_delete(matrix: *mut LedMatrix);
@@ -383,3 +453,72 @@ extern "C" {
         b: u8,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsv_primary_colors() {
+        assert_eq!(
+            LedColor::from_hsv(0.0, 1.0, 1.0),
+            LedColor { red: 255, green: 0, blue: 0 }
+        );
+        assert_eq!(
+            LedColor::from_hsv(120.0, 1.0, 1.0),
+            LedColor { red: 0, green: 255, blue: 0 }
+        );
+        assert_eq!(
+            LedColor::from_hsv(240.0, 1.0, 1.0),
+            LedColor { red: 0, green: 0, blue: 255 }
+        );
+    }
+
+    #[test]
+    fn hsv_wraps_hue() {
+        assert_eq!(LedColor::from_hsv(0.0, 1.0, 1.0), LedColor::from_hsv(360.0, 1.0, 1.0));
+        assert_eq!(LedColor::from_hsv(0.0, 1.0, 1.0), LedColor::from_hsv(-360.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn hsv_zero_value_is_black() {
+        assert_eq!(
+            LedColor::from_hsv(200.0, 1.0, 0.0),
+            LedColor { red: 0, green: 0, blue: 0 }
+        );
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let color = LedColor { red: 0x12, green: 0x34, blue: 0x56 };
+        assert_eq!(color.to_hex(), 0x123456);
+        assert_eq!(LedColor::from_hex(0x123456), color);
+    }
+
+    #[test]
+    fn lerp_endpoints_and_midpoint() {
+        let black = LedColor { red: 0, green: 0, blue: 0 };
+        let white = LedColor { red: 255, green: 255, blue: 255 };
+
+        assert_eq!(black.lerp(&white, 0.0), black);
+        assert_eq!(black.lerp(&white, 1.0), white);
+        assert_eq!(
+            black.lerp(&white, 0.5),
+            LedColor { red: 128, green: 128, blue: 128 }
+        );
+    }
+
+    #[test]
+    fn scale_brightness_clamps() {
+        let color = LedColor { red: 200, green: 100, blue: 10 };
+
+        assert_eq!(
+            color.scale_brightness(2.0),
+            LedColor { red: 255, green: 200, blue: 20 }
+        );
+        assert_eq!(
+            color.scale_brightness(0.0),
+            LedColor { red: 0, green: 0, blue: 0 }
+        );
+    }
+}
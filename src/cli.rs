@@ -0,0 +1,199 @@
+//! Optional `clap`-based argument parsing for the standard matrix and
+//! runtime flags used by every command-line tool built on this library.
+
+use clap::{value_parser, Arg, ArgMatches, Command};
+
+use crate::led_matrix_options::{Multiplexing, Options, RowAddressType, RuntimeOptions, ScanMode};
+
+/// Registers the standard `--led-*` matrix flags onto `cmd`.
+pub fn add_matrix_flags(cmd: Command) -> Command {
+    cmd.arg(
+        Arg::new("led-gpio-mapping")
+            .long("led-gpio-mapping")
+            .value_name("NAME")
+            .help("Name of the GPIO mapping used"),
+    )
+    .arg(
+        Arg::new("led-rows")
+            .long("led-rows")
+            .value_name("ROWS")
+            .value_parser(value_parser!(u16))
+            .help("Panel rows, typically 8, 16, 32 or 64"),
+    )
+    .arg(
+        Arg::new("led-cols")
+            .long("led-cols")
+            .value_name("COLS")
+            .value_parser(value_parser!(u16))
+            .help("Panel columns, typically 32 or 64"),
+    )
+    .arg(
+        Arg::new("led-chain")
+            .long("led-chain")
+            .value_name("CHAIN")
+            .value_parser(value_parser!(u16))
+            .help("Number of daisy-chained boards"),
+    )
+    .arg(
+        Arg::new("led-parallel")
+            .long("led-parallel")
+            .value_name("PARALLEL")
+            .value_parser(value_parser!(u16))
+            .help("Number of parallel chains (1-6)"),
+    )
+    .arg(
+        Arg::new("led-multiplexing")
+            .long("led-multiplexing")
+            .value_name("0..6")
+            .value_parser(value_parser!(u8))
+            .help("Multiplexing type: 0=direct, 1=stripe, 2=checker, 3=spiral, 4=z-stripe, 5=z-stripe-mirror, 6=coreman"),
+    )
+    .arg(
+        Arg::new("led-pwm-bits")
+            .long("led-pwm-bits")
+            .value_name("BITS")
+            .value_parser(value_parser!(u8))
+            .help("PWM bits, 0-11"),
+    )
+    .arg(
+        Arg::new("led-brightness")
+            .long("led-brightness")
+            .value_name("PERCENT")
+            .value_parser(value_parser!(u8))
+            .help("Brightness in percent, 1-100"),
+    )
+    .arg(
+        Arg::new("led-rgb-sequence")
+            .long("led-rgb-sequence")
+            .value_name("SEQUENCE")
+            .help("Mapping of the RGB sequence, e.g. RGB or BGR"),
+    )
+    .arg(
+        Arg::new("led-pixel-mapper")
+            .long("led-pixel-mapper")
+            .value_name("CONFIG")
+            .help("Pixel mapper configuration string"),
+    )
+    .arg(
+        Arg::new("led-panel-type")
+            .long("led-panel-type")
+            .value_name("TYPE")
+            .help("Panel initialisation sequence needed by some panels"),
+    )
+    .arg(
+        Arg::new("led-scan-mode")
+            .long("led-scan-mode")
+            .value_name("0|1")
+            .value_parser(value_parser!(u8))
+            .help("Scan mode: 0=progressive, 1=interlaced"),
+    )
+    .arg(
+        Arg::new("led-row-addr-type")
+            .long("led-row-addr-type")
+            .value_name("0..3")
+            .value_parser(value_parser!(u8))
+            .help("Row address type: 0=direct, 1=shift-register, 2=direct-abcd-line, 3=abc-shift-register"),
+    )
+    .arg(
+        Arg::new("led-show-refresh")
+            .long("led-show-refresh")
+            .action(clap::ArgAction::SetTrue)
+            .help("Show the refresh rate on stderr"),
+    )
+    .arg(
+        Arg::new("led-slowdown-gpio")
+            .long("led-slowdown-gpio")
+            .value_name("0..4")
+            .value_parser(value_parser!(u32))
+            .help("How much to slow down writing to the GPIO pins"),
+    )
+}
+
+/// Builds an [Options] from the matches produced by a [Command] that was
+/// passed through [add_matrix_flags].
+pub fn matrix_options_from_matches(matches: &ArgMatches) -> Result<Options, &'static str> {
+    let mut options = Options::new();
+
+    if let Some(mapping) = matches.get_one::<String>("led-gpio-mapping") {
+        options.set_hardware_mapping(mapping);
+    }
+    if let Some(&rows) = matches.get_one::<u16>("led-rows") {
+        options.set_rows(rows);
+    }
+    if let Some(&cols) = matches.get_one::<u16>("led-cols") {
+        options.set_cols(cols);
+    }
+    if let Some(&chain) = matches.get_one::<u16>("led-chain") {
+        options.set_chain_length(chain);
+    }
+    if let Some(&parallel) = matches.get_one::<u16>("led-parallel") {
+        options.set_parallel(parallel).map_err(|_| "led-parallel out of range")?;
+    }
+    if let Some(&multiplexing) = matches.get_one::<u8>("led-multiplexing") {
+        let multiplexing = match multiplexing {
+            0 => Multiplexing::Direct,
+            1 => Multiplexing::Stripe,
+            2 => Multiplexing::Checkered,
+            3 => Multiplexing::Spiral,
+            4 => Multiplexing::ZStripe,
+            5 => Multiplexing::ZnMirrorZStripe,
+            6 => Multiplexing::Coreman,
+            _ => return Err("led-multiplexing out of range (0-6)"),
+        };
+        options.set_multiplexing(multiplexing);
+    }
+    if let Some(&pwm_bits) = matches.get_one::<u8>("led-pwm-bits") {
+        options.set_pwm_bits(pwm_bits).map_err(|_| "led-pwm-bits out of range")?;
+    }
+    if let Some(&brightness) = matches.get_one::<u8>("led-brightness") {
+        options.set_brightness(brightness).map_err(|_| "led-brightness out of range")?;
+    }
+    if let Some(sequence) = matches.get_one::<String>("led-rgb-sequence") {
+        options.set_led_rgb_sequence(sequence);
+    }
+    if let Some(pixel_mapper) = matches.get_one::<String>("led-pixel-mapper") {
+        options.set_pixel_mapper_config(pixel_mapper);
+    }
+    if let Some(panel_type) = matches.get_one::<String>("led-panel-type") {
+        options.set_panel_type(panel_type);
+    }
+    if let Some(&scan_mode) = matches.get_one::<u8>("led-scan-mode") {
+        let scan_mode = match scan_mode {
+            0 => ScanMode::Progressive,
+            1 => ScanMode::Interlaced,
+            _ => return Err("led-scan-mode out of range (0-1)"),
+        };
+        options.set_scan_mode(scan_mode);
+    }
+    if let Some(&row_addr_type) = matches.get_one::<u8>("led-row-addr-type") {
+        let row_addr_type = match row_addr_type {
+            0 => RowAddressType::Direct,
+            1 => RowAddressType::ShiftRegister,
+            2 => RowAddressType::DirectABCDLine,
+            3 => RowAddressType::ABCShiftRegister,
+            _ => return Err("led-row-addr-type out of range (0-3)"),
+        };
+        options.set_row_address_type(row_addr_type);
+    }
+    if matches.get_flag("led-show-refresh") {
+        options.set_show_refresh_rate(true);
+    }
+
+    Ok(options)
+}
+
+/// Builds a [RuntimeOptions] from the matches produced by a [Command] that
+/// was passed through [add_matrix_flags]. Kept separate from
+/// [matrix_options_from_matches] since [RuntimeOptions] covers process-level
+/// behaviour rather than panel configuration.
+pub fn runtime_options_from_matches(matches: &ArgMatches) -> Result<RuntimeOptions, &'static str> {
+    let mut runtime_options = RuntimeOptions::new();
+
+    if let Some(&slowdown_gpio) = matches.get_one::<u32>("led-slowdown-gpio") {
+        runtime_options
+            .set_gpio_slowdown(slowdown_gpio)
+            .map_err(|_| "led-slowdown-gpio out of range")?;
+    }
+
+    Ok(runtime_options)
+}
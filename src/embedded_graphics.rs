@@ -0,0 +1,61 @@
+//! Integration with the `embedded-graphics` crate, allowing its shapes,
+//! fonts and images to be drawn directly onto a [LedCanvas].
+
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, Pixel as EgPixel};
+
+use crate::c::LedColor;
+use crate::{LedCanvas, Pixel};
+
+impl From<Rgb888> for LedColor {
+    fn from(color: Rgb888) -> Self {
+        LedColor {
+            red: color.r(),
+            green: color.g(),
+            blue: color.b(),
+        }
+    }
+}
+
+impl From<LedColor> for Rgb888 {
+    fn from(color: LedColor) -> Self {
+        Rgb888::new(color.red, color.green, color.blue)
+    }
+}
+
+impl OriginDimensions for LedCanvas {
+    fn size(&self) -> Size {
+        let (width, height) = LedCanvas::size(self);
+        Size::new(width as u32, height as u32)
+    }
+}
+
+impl DrawTarget for LedCanvas {
+    type Color = Rgb888;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = EgPixel<Self::Color>>,
+    {
+        let (width, height) = LedCanvas::size(self);
+
+        for EgPixel(point, color) in pixels {
+            if point.x >= 0 && point.x < width && point.y >= 0 && point.y < height {
+                self.set(
+                    Pixel {
+                        x: point.x,
+                        y: point.y,
+                    },
+                    &color.into(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        LedCanvas::fill(self, &color.into());
+        Ok(())
+    }
+}
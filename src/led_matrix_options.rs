@@ -30,8 +30,60 @@ pub enum Multiplexing {
     Coreman = 6,
 }
 
+/// A single entry in a pixel-mapper chain, as accepted by
+/// [Options::set_pixel_mappers]. Mappers are applied in the order given.
+pub enum PixelMapper {
+    /// Rotates the image by the given angle, which must be a multiple of 90
+    /// degrees.
+    Rotate(u16),
+    /// Arranges the matrix as interleaved U-shaped chains.
+    UMapper,
+    /// Arranges the matrix as interleaved V-shaped chains.
+    VMapper,
+    /// Mirrors the image horizontally and/or vertically.
+    Mirror { horizontal: bool, vertical: bool },
+    /// An escape hatch for passing a raw, unvalidated mapper config entry
+    /// straight through to the underlying library.
+    Raw(String),
+}
+
+impl PixelMapper {
+    fn to_config_string(&self) -> result::Result<String, PixelMapperError> {
+        match self {
+            PixelMapper::Rotate(angle) => {
+                if angle % 90 != 0 {
+                    return Err(PixelMapperError::InvalidRotation(*angle));
+                }
+                Ok(format!("Rotate:{}", angle))
+            }
+            PixelMapper::UMapper => Ok("U-mapper".to_string()),
+            PixelMapper::VMapper => Ok("V-mapper".to_string()),
+            PixelMapper::Mirror {
+                horizontal,
+                vertical,
+            } => match (horizontal, vertical) {
+                (true, true) => Ok("Mirror:HV".to_string()),
+                (true, false) => Ok("Mirror:H".to_string()),
+                (false, true) => Ok("Mirror:V".to_string()),
+                (false, false) => Err(PixelMapperError::EmptyMirror),
+            },
+            PixelMapper::Raw(raw) => Ok(raw.clone()),
+        }
+    }
+}
+
 ////////////////////////////// Error Structs //////////////////////////////
 
+/// The error type for [Options::set_pixel_mappers].
+#[derive(Debug, Clone)]
+pub enum PixelMapperError {
+    /// A [PixelMapper::Rotate] angle was not a multiple of 90 degrees.
+    InvalidRotation(u16),
+    /// A [PixelMapper::Mirror] had neither axis set, which the underlying
+    /// library has no representation for.
+    EmptyMirror,
+}
+
 /// The error type for the parallel count setter. This is returned if the value
 /// for the parallel count is out of range.
 #[derive(Debug, Clone)]
@@ -128,9 +180,10 @@ impl Options {
     }
 
     /// The number of parallel chains connected together. Old Pis with 26 pins
-    /// only support 1, but newer Pis with 40 pins can support up to 3.
+    /// only support 1, 40-pin Pis support up to 3, and the Compute Module
+    /// supports up to 6.
     pub fn set_parallel(&mut self, parallel: u16) -> Result<ParallelError> {
-        if parallel < 1 || parallel > 3 {
+        if parallel < 1 || parallel > 6 {
             Err(ParallelError { parallel })
         } else {
             self.parallel = parallel as c_int;
@@ -206,6 +259,20 @@ impl Options {
         }
     }
 
+    /// A typed, validated alternative to [Options::set_pixel_mapper_config],
+    /// serializing `mappers` to the semicolon-separated config string the
+    /// underlying library expects.
+    pub fn set_pixel_mappers(&mut self, mappers: &[PixelMapper]) -> Result<PixelMapperError> {
+        let mut parts = Vec::with_capacity(mappers.len());
+
+        for mapper in mappers {
+            parts.push(mapper.to_config_string()?);
+        }
+
+        self.set_pixel_mapper_config(&parts.join(";"));
+        Ok(())
+    }
+
     /// The panel type. Normally just an empty string, but certain panels
     /// require an initialisation sequence.
     pub fn set_panel_type(&mut self, panel_type: &str) {
@@ -252,13 +319,105 @@ impl Drop for Options {
     }
 }
 
+////////////////////////////// LED Runtime Options Impl //////////////////////////////
+
+/// Runtime-only parameters for creating a matrix, as opposed to the panel
+/// configuration carried by [Options]. These cover process-level behaviour
+/// such as GPIO timing, daemonizing and privilege dropping.
+#[repr(C, packed)]
+pub struct RuntimeOptions {
+    gpio_slowdown: c_int,
+    daemon: c_int,
+    drop_privileges: c_int,
+    do_gpio_init: c_uint,
+}
+
+impl RuntimeOptions {
+    /// Constructs a new RuntimeOptions with the default values
+    /// pre-configured. `daemon` and `drop_privileges` default to "leave to
+    /// the library's own default" (see [RuntimeOptions::set_daemon] and
+    /// [RuntimeOptions::set_drop_privileges]).
+    pub fn new() -> RuntimeOptions {
+        RuntimeOptions {
+            gpio_slowdown: 1,
+            daemon: -1,
+            drop_privileges: -1,
+            do_gpio_init: 1,
+        }
+    }
+
+    /// How much to slow down writing to the GPIO pins, in the range 0..4
+    /// inclusive. Higher values are needed on faster Pis (e.g. the Pi 4)
+    /// where the panel can't otherwise keep up.
+    pub fn set_gpio_slowdown(&mut self, gpio_slowdown: u32) -> Result<GpioSlowdownError> {
+        if gpio_slowdown > 4 {
+            Err(GpioSlowdownError { gpio_slowdown })
+        } else {
+            self.gpio_slowdown = gpio_slowdown as c_int;
+            Ok(())
+        }
+    }
+
+    /// Whether to daemonize the process once the matrix has been set up.
+    /// `None` leaves the decision to the underlying library's own default.
+    pub fn set_daemon(&mut self, daemon: Option<bool>) {
+        self.daemon = tri_state(daemon);
+    }
+
+    /// Whether to drop privileges from root down to the `daemon` user once
+    /// the GPIO pins have been configured. `None` leaves the decision to the
+    /// underlying library's own default.
+    pub fn set_drop_privileges(&mut self, drop_privileges: Option<bool>) {
+        self.drop_privileges = tri_state(drop_privileges);
+    }
+
+    /// Whether this library should perform the GPIO setup at all. Only
+    /// disable this if something else in the process has already done so.
+    pub fn set_do_gpio_init(&mut self, do_gpio_init: bool) {
+        self.do_gpio_init = do_gpio_init as c_uint;
+    }
+}
+
+/// Maps an optional boolean onto the C library's tri-state convention:
+/// `-1` for "unset", `0` for false and `1` for true.
+fn tri_state(value: Option<bool>) -> c_int {
+    match value {
+        None => -1,
+        Some(false) => 0,
+        Some(true) => 1,
+    }
+}
+
+/// The error type for the GPIO slowdown setter. This is returned if the
+/// value for the GPIO slowdown is out of range.
+#[derive(Debug, Clone)]
+pub struct GpioSlowdownError {
+    gpio_slowdown: u32,
+}
+
+impl fmt::Display for GpioSlowdownError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "GPIO slowdown {} out of range (>= 0, <= 4)",
+            self.gpio_slowdown
+        )
+    }
+}
+
+impl error::Error for GpioSlowdownError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
 ////////////////////////////// Error Struct Impls //////////////////////////////
 
 impl fmt::Display for ParallelError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "parallel count {} out of range (>= 1, <= 3)",
+            "parallel count {} out of range (>= 1, <= 6)",
             self.parallel
         )
     }
@@ -297,3 +456,158 @@ impl error::Error for BrightnessError {
         None
     }
 }
+
+impl fmt::Display for PixelMapperError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PixelMapperError::InvalidRotation(angle) => {
+                write!(f, "rotation angle {} is not a multiple of 90 degrees", angle)
+            }
+            PixelMapperError::EmptyMirror => {
+                write!(f, "mirror mapper must mirror at least one axis")
+            }
+        }
+    }
+}
+
+impl error::Error for PixelMapperError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_accepts_multiples_of_90() {
+        assert_eq!(PixelMapper::Rotate(0).to_config_string().unwrap(), "Rotate:0");
+        assert_eq!(PixelMapper::Rotate(90).to_config_string().unwrap(), "Rotate:90");
+        assert_eq!(PixelMapper::Rotate(270).to_config_string().unwrap(), "Rotate:270");
+    }
+
+    #[test]
+    fn rotate_rejects_non_multiples_of_90() {
+        assert!(matches!(
+            PixelMapper::Rotate(45).to_config_string(),
+            Err(PixelMapperError::InvalidRotation(45))
+        ));
+    }
+
+    #[test]
+    fn mirror_combinations() {
+        assert_eq!(
+            PixelMapper::Mirror { horizontal: true, vertical: true }
+                .to_config_string()
+                .unwrap(),
+            "Mirror:HV"
+        );
+        assert_eq!(
+            PixelMapper::Mirror { horizontal: true, vertical: false }
+                .to_config_string()
+                .unwrap(),
+            "Mirror:H"
+        );
+        assert_eq!(
+            PixelMapper::Mirror { horizontal: false, vertical: true }
+                .to_config_string()
+                .unwrap(),
+            "Mirror:V"
+        );
+        assert!(matches!(
+            PixelMapper::Mirror { horizontal: false, vertical: false }.to_config_string(),
+            Err(PixelMapperError::EmptyMirror)
+        ));
+    }
+
+    #[test]
+    fn u_v_and_raw_mappers() {
+        assert_eq!(PixelMapper::UMapper.to_config_string().unwrap(), "U-mapper");
+        assert_eq!(PixelMapper::VMapper.to_config_string().unwrap(), "V-mapper");
+        assert_eq!(
+            PixelMapper::Raw("Custom:1".to_string()).to_config_string().unwrap(),
+            "Custom:1"
+        );
+    }
+
+    #[test]
+    fn set_pixel_mappers_joins_with_semicolons() {
+        let mut options = Options::new();
+        options
+            .set_pixel_mappers(&[PixelMapper::Rotate(90), PixelMapper::UMapper])
+            .unwrap();
+        let config = unsafe { std::ffi::CStr::from_ptr({ options.pixel_mapper_config }) };
+        assert_eq!(config.to_str().unwrap(), "Rotate:90;U-mapper");
+    }
+
+    #[test]
+    fn set_pixel_mappers_propagates_first_error() {
+        let mut options = Options::new();
+        assert!(matches!(
+            options.set_pixel_mappers(&[PixelMapper::Rotate(45)]),
+            Err(PixelMapperError::InvalidRotation(45))
+        ));
+    }
+
+    #[test]
+    fn tri_state_mapping() {
+        assert_eq!(tri_state(None), -1);
+        assert_eq!(tri_state(Some(false)), 0);
+        assert_eq!(tri_state(Some(true)), 1);
+    }
+
+    #[test]
+    fn runtime_options_daemon_tri_state() {
+        let mut runtime_options = RuntimeOptions::new();
+
+        runtime_options.set_daemon(Some(true));
+        assert_eq!({ runtime_options.daemon }, 1);
+        runtime_options.set_daemon(Some(false));
+        assert_eq!({ runtime_options.daemon }, 0);
+        runtime_options.set_daemon(None);
+        assert_eq!({ runtime_options.daemon }, -1);
+    }
+
+    #[test]
+    fn runtime_options_drop_privileges_tri_state() {
+        let mut runtime_options = RuntimeOptions::new();
+
+        runtime_options.set_drop_privileges(Some(true));
+        assert_eq!({ runtime_options.drop_privileges }, 1);
+        runtime_options.set_drop_privileges(Some(false));
+        assert_eq!({ runtime_options.drop_privileges }, 0);
+        runtime_options.set_drop_privileges(None);
+        assert_eq!({ runtime_options.drop_privileges }, -1);
+    }
+
+    #[test]
+    fn gpio_slowdown_accepts_0_to_4() {
+        let mut runtime_options = RuntimeOptions::new();
+        assert!(runtime_options.set_gpio_slowdown(0).is_ok());
+        assert!(runtime_options.set_gpio_slowdown(4).is_ok());
+    }
+
+    #[test]
+    fn gpio_slowdown_rejects_above_4() {
+        let mut runtime_options = RuntimeOptions::new();
+        assert!(matches!(
+            runtime_options.set_gpio_slowdown(5),
+            Err(GpioSlowdownError { .. })
+        ));
+    }
+
+    #[test]
+    fn set_parallel_accepts_1_to_6() {
+        let mut options = Options::new();
+        assert!(options.set_parallel(1).is_ok());
+        assert!(options.set_parallel(6).is_ok());
+    }
+
+    #[test]
+    fn set_parallel_rejects_out_of_range() {
+        let mut options = Options::new();
+        assert!(options.set_parallel(0).is_err());
+        assert!(options.set_parallel(7).is_err());
+    }
+}
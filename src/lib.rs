@@ -1,14 +1,21 @@
 extern crate libc;
 
 mod c;
+#[cfg(feature = "clap")]
+pub mod cli;
+#[cfg(feature = "embedded-graphics")]
+mod embedded_graphics;
 pub mod led_matrix_options;
+#[cfg(feature = "pixelflut")]
+pub mod server;
 
-use libc::{c_char, c_int};
+use libc::c_int;
 use std::error;
 use std::ffi::CString;
 use std::fmt;
 use std::path::Path;
-use std::ptr::null;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub use c::LedColor;
 
@@ -37,11 +44,27 @@ pub struct LedFont {
     handle: *mut c::LedFont,
 }
 
+/// The per-frame context passed to the closure driving [LedMatrix::animate].
+#[derive(Debug, Clone, Copy)]
+pub struct FrameContext {
+    /// Time elapsed since the animation started.
+    pub elapsed: Duration,
+    /// The index of the current frame, starting at 0.
+    pub frame: u64,
+}
+
 /// The error type for [LedMatrix::new] which is returned upon failure to create
 /// a new [LedMatrix].
 #[derive(Debug, Clone)]
 pub struct NewMatrixError;
 
+/// The error type for [LedMatrix::animate], returned if `target_fps` is not
+/// a positive, finite value.
+#[derive(Debug, Clone)]
+pub struct AnimateError {
+    target_fps: f64,
+}
+
 /// The error type for [LedFont::new] which is returned upon failure to create
 /// a new [LedFont].
 #[derive(Debug, Clone)]
@@ -62,6 +85,18 @@ impl LedMatrix {
     /// Attempts to construct a new LED matrix with the provided options (or the
     /// default options).
     pub fn new(options: Option<led_matrix_options::Options>) -> Result<LedMatrix, NewMatrixError> {
+        LedMatrix::new_with_runtime_options(options, None)
+    }
+
+    /// Attempts to construct a new LED matrix with the provided options and
+    /// runtime options (or the default of each). The runtime options cover
+    /// process-level behaviour such as GPIO slowdown, daemonizing and
+    /// privilege dropping, none of which can be expressed through
+    /// [led_matrix_options::Options] alone.
+    pub fn new_with_runtime_options(
+        options: Option<led_matrix_options::Options>,
+        runtime_options: Option<led_matrix_options::RuntimeOptions>,
+    ) -> Result<LedMatrix, NewMatrixError> {
         let options = {
             if let Some(o) = options {
                 o
@@ -69,12 +104,18 @@ impl LedMatrix {
                 led_matrix_options::Options::new()
             }
         };
+        let runtime_options = {
+            if let Some(o) = runtime_options {
+                o
+            } else {
+                led_matrix_options::RuntimeOptions::new()
+            }
+        };
 
         let handle = unsafe {
-            c::led_matrix_create_from_options(
+            c::led_matrix_create_from_options_and_rt_options(
                 &options as *const led_matrix_options::Options,
-                null::<c_int>() as *mut c_int,
-                null::<c_char>() as *mut *mut *mut c_char,
+                &runtime_options as *const led_matrix_options::RuntimeOptions,
             )
         };
 
@@ -110,8 +151,67 @@ impl LedMatrix {
 
         LedCanvas { handle }
     }
+
+    /// Runs an animation loop that owns the offscreen-canvas swap dance.
+    /// Each frame, `draw` is given a freshly cleared canvas and a
+    /// [FrameContext] carrying the elapsed time and frame index; returning
+    /// `false` stops the loop. Frames are paced to `target_fps` by sleeping
+    /// the remainder of the frame budget after `draw` returns. Returns the
+    /// measured average FPS over the whole run, or an [AnimateError] if
+    /// `target_fps` is not a positive, finite value.
+    pub fn animate<F>(&self, target_fps: f64, mut draw: F) -> Result<f64, AnimateError>
+    where
+        F: FnMut(&mut LedCanvas, FrameContext) -> bool,
+    {
+        if !target_fps.is_finite() || target_fps <= 0.0 {
+            return Err(AnimateError { target_fps });
+        }
+
+        let frame_duration = Duration::from_secs_f64(1.0 / target_fps);
+        let start = Instant::now();
+        let mut canvas = self.offscreen_canvas();
+        let mut frame = 0u64;
+
+        loop {
+            let frame_start = Instant::now();
+            canvas.clear();
+
+            let context = FrameContext {
+                elapsed: start.elapsed(),
+                frame,
+            };
+            if !draw(&mut canvas, context) {
+                break;
+            }
+
+            canvas = self.swap(canvas);
+
+            let draw_time = frame_start.elapsed();
+            if draw_time < frame_duration {
+                thread::sleep(frame_duration - draw_time);
+            }
+
+            frame += 1;
+        }
+
+        let total = start.elapsed().as_secs_f64();
+        Ok(if total > 0.0 {
+            frame as f64 / total
+        } else {
+            0.0
+        })
+    }
 }
 
+// The underlying C library keeps no thread-local state; a matrix/canvas
+// handle may safely be moved to, and used exclusively from, another
+// thread, which the Pixelflut server relies on to own them from its render
+// thread.
+#[cfg(feature = "pixelflut")]
+unsafe impl Send for LedMatrix {}
+#[cfg(feature = "pixelflut")]
+unsafe impl Send for LedCanvas {}
+
 impl Drop for LedMatrix {
     fn drop(&mut self) {
         unsafe {
@@ -274,6 +374,22 @@ impl error::Error for NewMatrixError {
     }
 }
 
+impl fmt::Display for AnimateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "target fps {} must be positive and finite",
+            self.target_fps
+        )
+    }
+}
+
+impl error::Error for AnimateError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
 impl fmt::Display for NewFontError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "failed to create LED font")
@@ -463,4 +579,14 @@ mod tests {
         matrix.swap(canvas);
         thread::sleep(time::Duration::new(0, 500000000));
     }
+
+    #[test]
+    fn animate_rejects_non_positive_fps() {
+        let matrix = led_matrix();
+
+        assert!(matrix.animate(0.0, |_, _| false).is_err());
+        assert!(matrix.animate(-30.0, |_, _| false).is_err());
+        assert!(matrix.animate(f64::NAN, |_, _| false).is_err());
+        assert!(matrix.animate(f64::INFINITY, |_, _| false).is_err());
+    }
 }
@@ -0,0 +1,238 @@
+//! An optional Pixelflut server that lets the matrix be drawn on over the
+//! network. See <https://github.com/defnull/pixelflut> for the protocol.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::c::LedColor;
+use crate::{LedCanvas, LedMatrix, Pixel};
+
+/// Shared, mutex-guarded drawing state. The canvas itself is held as an
+/// [Option] so the render thread can briefly take ownership of it to hand
+/// it to [LedMatrix::swap], which consumes its argument by value.
+struct SharedState {
+    canvas: Option<LedCanvas>,
+    pixels: Vec<LedColor>,
+    width: i32,
+    height: i32,
+}
+
+/// A Pixelflut server that draws pixels received over TCP onto an
+/// [LedMatrix]'s offscreen canvas, presenting a new frame at a fixed
+/// interval.
+pub struct PixelflutServer {
+    state: Arc<Mutex<SharedState>>,
+}
+
+impl PixelflutServer {
+    /// Creates a Pixelflut server and starts a render thread that swaps the
+    /// shared canvas onto `matrix` every `frame_interval`. No socket is bound
+    /// here: [PixelflutServer::accept_loop] binds to an address and services
+    /// connections.
+    pub fn start(matrix: LedMatrix, frame_interval: Duration) -> PixelflutServer {
+        let canvas = matrix.offscreen_canvas();
+        let (width, height) = canvas.size();
+        let state = Arc::new(Mutex::new(SharedState {
+            canvas: Some(canvas),
+            pixels: vec![
+                LedColor {
+                    red: 0,
+                    green: 0,
+                    blue: 0
+                };
+                (width * height).max(0) as usize
+            ],
+            width,
+            height,
+        }));
+
+        let render_state = Arc::clone(&state);
+        thread::spawn(move || loop {
+            thread::sleep(frame_interval);
+
+            let mut locked = render_state.lock().unwrap();
+            let canvas = locked.canvas.take().expect("canvas missing from shared state");
+            let mut next = matrix.swap(canvas);
+
+            // `next` is the other physical buffer, last painted two render
+            // ticks ago, so it must be brought back in sync with the
+            // authoritative `pixels` array before it can be swapped in again.
+            let width = locked.width;
+            for (i, color) in locked.pixels.iter().enumerate() {
+                let x = i as i32 % width;
+                let y = i as i32 / width;
+                next.set(Pixel { x, y }, color);
+            }
+
+            locked.canvas = Some(next);
+        });
+
+        PixelflutServer { state }
+    }
+
+    /// Listens on `addr`, spawning a thread per connection that parses and
+    /// applies Pixelflut commands. This call blocks until binding `addr` or
+    /// accepting a connection fails, and then returns that error.
+    pub fn accept_loop<A: ToSocketAddrs>(&self, addr: A) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let state = Arc::clone(&self.state);
+            thread::spawn(move || handle_connection(stream, state));
+        }
+
+        Ok(())
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: Arc<Mutex<SharedState>>) {
+    let mut writer = match stream.try_clone() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let reader = BufReader::new(stream);
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => return,
+        };
+
+        if let Some(response) = handle_command(&line, &state) {
+            if writer.write_all(response.as_bytes()).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn handle_command(line: &str, state: &Arc<Mutex<SharedState>>) -> Option<String> {
+    let mut parts = line.split_whitespace();
+
+    match parts.next()? {
+        "SIZE" => {
+            let locked = state.lock().unwrap();
+            Some(format!("SIZE {} {}\n", locked.width, locked.height))
+        }
+        "PX" => {
+            let x: i32 = parts.next()?.parse().ok()?;
+            let y: i32 = parts.next()?.parse().ok()?;
+
+            let mut locked = state.lock().unwrap();
+            if x < 0 || y < 0 || x >= locked.width || y >= locked.height {
+                return None;
+            }
+            let index = (y * locked.width + x) as usize;
+
+            match parts.next() {
+                Some(color) => {
+                    let (r, g, b, a) = parse_color(color)?;
+                    let existing = locked.pixels[index];
+                    let blended = blend(existing, LedColor { red: r, green: g, blue: b }, a);
+
+                    locked.pixels[index] = blended;
+                    let canvas = locked.canvas.as_mut().expect("canvas missing from shared state");
+                    canvas.set(Pixel { x, y }, &blended);
+                    None
+                }
+                None => {
+                    let color = locked.pixels[index];
+                    Some(format!(
+                        "PX {} {} {:02x}{:02x}{:02x}\n",
+                        x, y, color.red, color.green, color.blue
+                    ))
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Parses a 6- or 8-digit hex colour (`rrggbb` or `rrggbbaa`, matched
+/// case-insensitively) into its red, green, blue and alpha (defaulting to
+/// fully opaque) components.
+fn parse_color(hex: &str) -> Option<(u8, u8, u8, u8)> {
+    if !hex.is_ascii() {
+        return None;
+    }
+
+    let bytes = match hex.len() {
+        6 => u32::from_str_radix(hex, 16).ok()? << 8 | 0xff,
+        8 => u32::from_str_radix(hex, 16).ok()?,
+        _ => return None,
+    };
+
+    Some((
+        (bytes >> 24) as u8,
+        (bytes >> 16) as u8,
+        (bytes >> 8) as u8,
+        bytes as u8,
+    ))
+}
+
+fn blend(existing: LedColor, incoming: LedColor, alpha: u8) -> LedColor {
+    let a = alpha as u32;
+    let blend_channel = |old: u8, new: u8| -> u8 {
+        (((new as u32) * a + (old as u32) * (255 - a)) / 255) as u8
+    };
+
+    LedColor {
+        red: blend_channel(existing.red, incoming.red),
+        green: blend_channel(existing.green, incoming.green),
+        blue: blend_channel(existing.blue, incoming.blue),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_color_six_digit_is_fully_opaque() {
+        assert_eq!(parse_color("ff8000"), Some((0xff, 0x80, 0x00, 0xff)));
+    }
+
+    #[test]
+    fn parse_color_eight_digit_carries_alpha() {
+        assert_eq!(parse_color("ff800080"), Some((0xff, 0x80, 0x00, 0x80)));
+    }
+
+    #[test]
+    fn parse_color_rejects_invalid_length_and_digits() {
+        assert_eq!(parse_color("ff80"), None);
+        assert_eq!(parse_color("zzzzzz"), None);
+    }
+
+    #[test]
+    fn parse_color_rejects_non_ascii() {
+        assert_eq!(parse_color("ff80€0"), None);
+    }
+
+    #[test]
+    fn blend_fully_opaque_replaces_existing() {
+        let existing = LedColor { red: 10, green: 20, blue: 30 };
+        let incoming = LedColor { red: 200, green: 100, blue: 50 };
+
+        assert_eq!(blend(existing, incoming, 255), incoming);
+    }
+
+    #[test]
+    fn blend_fully_transparent_keeps_existing() {
+        let existing = LedColor { red: 10, green: 20, blue: 30 };
+        let incoming = LedColor { red: 200, green: 100, blue: 50 };
+
+        assert_eq!(blend(existing, incoming, 0), existing);
+    }
+
+    #[test]
+    fn blend_half_alpha_averages() {
+        let existing = LedColor { red: 0, green: 0, blue: 0 };
+        let incoming = LedColor { red: 255, green: 255, blue: 255 };
+
+        assert_eq!(blend(existing, incoming, 128), LedColor { red: 128, green: 128, blue: 128 });
+    }
+}